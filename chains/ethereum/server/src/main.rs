@@ -0,0 +1,8 @@
+use anyhow::Result;
+
+mod router;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    rosetta_server::main::<rosetta_server_ethereum::EthereumClient>().await
+}
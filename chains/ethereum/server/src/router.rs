@@ -0,0 +1,419 @@
+//! Deployment and control of the threshold-key "Router" contract, modeled on
+//! Serai's Ethereum integration. [`handle_call_request`] handles the
+//! `deployer.*`/`router.*` methods of a `/call` request, for a
+//! `rosetta_core::BlockchainClient::call` override to dispatch to; a
+//! connector's `/call` handler reaches it by calling `call()` on a
+//! `BlockchainClient` that dispatches to it. Nothing in this binary calls it.
+//!
+//! `rosetta_server_ethereum::EthereumClient` (the connector this binary
+//! actually runs, via `rosetta_server::main`) lives outside this repository,
+//! so wiring its `BlockchainClient::call` override to this module can't be
+//! done from here — that override has to be added on the `EthereumClient`
+//! side once it depends on this `call` method.
+use anyhow::{Context, Result};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey};
+use rosetta_types::CallRequest;
+use serde_json::{json, Value};
+
+/// Bytecode of the Router contract this deployer publishes. The deployed
+/// address depends only on the deployer's address and nonce (see
+/// [`Deployer`]), not on this code.
+///
+/// `contracts/router.bin` is currently a 0-byte placeholder — this repository
+/// doesn't have a Solidity toolchain to produce the real compiled artifact.
+/// [`Deployer::deploy_transaction`] refuses to build a deployment transaction
+/// while it's empty, rather than silently publishing a contract with no code.
+const ROUTER_BYTECODE: &[u8] = include_bytes!("../contracts/router.bin");
+
+/// Network parameters needed to build and sign a submittable transaction,
+/// independent of which Router method is being called.
+#[derive(Clone, Copy, Debug)]
+pub struct GasParams {
+    /// EIP-155 chain ID, mixed into the signature so a signed transaction
+    /// can't be replayed on a different chain.
+    pub chain_id: u64,
+    /// Gas price to pay, in wei.
+    pub gas_price: u128,
+    /// Gas limit to allow the transaction.
+    pub gas_limit: u64,
+}
+
+/// A signed, submittable transaction and the identifier it will be known by
+/// once broadcast.
+pub struct SubmittableTransaction {
+    /// Transaction hash (also the Rosetta transaction identifier).
+    pub hash: [u8; 32],
+    /// RLP-encoded signed transaction, ready for `eth_sendRawTransaction`.
+    pub raw: Vec<u8>,
+}
+
+/// Deterministically deploys and addresses the Router contract, and signs
+/// the relayer transactions that call it afterwards.
+///
+/// The Router's address is computed the same way as any other `CREATE`
+/// deployment (`keccak256(rlp(deployer_address, deployer_nonce))[12..]`),
+/// but because the Router is published at a single, fixed nonce of the
+/// deployer account (`deployer_nonce`), the Router's address can be computed
+/// and funded *before* it exists, without depending on its bytecode or
+/// constructor arguments (so it can't be front-run to a different address).
+pub struct Deployer {
+    /// Private key of the account that publishes the Router and relays
+    /// `router.*` calls to it.
+    deployer_key: [u8; 32],
+    /// Address derived from `deployer_key`.
+    deployer_address: [u8; 20],
+    /// Nonce the deployer account must be at to publish the Router.
+    deployer_nonce: u64,
+    /// Gas parameters used for every transaction this deployer signs.
+    gas: GasParams,
+}
+
+impl Deployer {
+    /// A deployer that will publish the Router from `deployer_key` at
+    /// `deployer_nonce`, signing with `gas`.
+    pub fn new(deployer_key: [u8; 32], deployer_nonce: u64, gas: GasParams) -> Result<Self> {
+        let deployer_address = address_of(&deployer_key)?;
+        Ok(Self {
+            deployer_key,
+            deployer_address,
+            deployer_nonce,
+            gas,
+        })
+    }
+
+    /// The Router's address, computable before it's deployed.
+    #[must_use]
+    pub fn router_address(&self) -> [u8; 20] {
+        contract_address(&self.deployer_address, self.deployer_nonce)
+    }
+
+    /// Build and sign the Router's deployment transaction.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `contracts/router.bin` is still the unpopulated placeholder,
+    /// rather than signing and returning a transaction that would deploy no
+    /// code at all.
+    pub fn deploy_transaction(&self) -> Result<SubmittableTransaction> {
+        anyhow::ensure!(
+            !ROUTER_BYTECODE.is_empty(),
+            "contracts/router.bin is a placeholder (0 bytes); replace it with the compiled \
+             Router contract bytecode before deploying"
+        );
+        let tx = Transaction {
+            nonce: self.deployer_nonce,
+            gas: self.gas,
+            to: None,
+            value: 0,
+            data: ROUTER_BYTECODE.to_vec(),
+        };
+        tx.sign(&self.deployer_key)
+    }
+
+    /// Build and sign a transaction calling `data` on `router`, relayed (and
+    /// paid for) by this deployer at `nonce`. The call's own authorization
+    /// (the Schnorr signature, for `router.updateKey`/`router.execute`) is
+    /// already embedded in `data`; this only covers the relaying account's
+    /// own transaction signature.
+    fn call_transaction(&self, router: [u8; 20], nonce: u64, data: Vec<u8>) -> Result<SubmittableTransaction> {
+        let tx = Transaction {
+            nonce,
+            gas: self.gas,
+            to: Some(router),
+            value: 0,
+            data,
+        };
+        tx.sign(&self.deployer_key)
+    }
+}
+
+/// Address an account will deploy a contract to via `CREATE` at `nonce`.
+fn contract_address(deployer: &[u8; 20], nonce: u64) -> [u8; 20] {
+    let mut stream = rlp::RlpStream::new_list(2);
+    stream.append(&deployer.as_slice());
+    stream.append(&nonce);
+    let hash = keccak256(&stream.out());
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// Address corresponding to a secp256k1 private key.
+fn address_of(private_key: &[u8; 32]) -> Result<[u8; 20]> {
+    let signing_key = SigningKey::from_bytes(private_key.into()).context("invalid private key")?;
+    let uncompressed = signing_key.verifying_key().to_encoded_point(false);
+    let hash = keccak256(&uncompressed.as_bytes()[1..]);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    Ok(address)
+}
+
+fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    use tiny_keccak::{Hasher, Keccak};
+    let mut hash = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(bytes);
+    keccak.finalize(&mut hash);
+    hash
+}
+
+/// An unsigned legacy (pre-EIP-1559) Ethereum transaction.
+struct Transaction {
+    nonce: u64,
+    gas: GasParams,
+    to: Option<[u8; 20]>,
+    value: u128,
+    data: Vec<u8>,
+}
+
+impl Transaction {
+    /// RLP-encode this transaction with `(v, r, s)` in place of the
+    /// signature fields: the unsigned form when `v = chain_id, r = s = []`
+    /// (EIP-155's signing payload), the signed form otherwise.
+    fn rlp_encode(&self, v: u64, r: &[u8], s: &[u8]) -> Vec<u8> {
+        let mut stream = rlp::RlpStream::new_list(9);
+        stream.append(&self.nonce);
+        stream.append(&self.gas.gas_price);
+        stream.append(&self.gas.gas_limit);
+        match &self.to {
+            Some(to) => stream.append(&to.as_slice()),
+            None => stream.append_empty_data(),
+        };
+        stream.append(&self.value);
+        stream.append(&self.data);
+        stream.append(&v);
+        stream.append(&r);
+        stream.append(&s);
+        stream.out().to_vec()
+    }
+
+    /// The EIP-155 hash this transaction's signature is computed over.
+    fn signing_hash(&self) -> [u8; 32] {
+        keccak256(&self.rlp_encode(self.gas.chain_id, &[], &[]))
+    }
+
+    /// Sign with `private_key`, returning the raw RLP-encoded transaction
+    /// ready to submit and its hash (the transaction identifier).
+    fn sign(&self, private_key: &[u8; 32]) -> Result<SubmittableTransaction> {
+        let signing_key = SigningKey::from_bytes(private_key.into()).context("invalid private key")?;
+        let hash = self.signing_hash();
+        let (signature, recovery_id): (Signature, RecoveryId) =
+            signing_key.sign_prehash_recoverable(&hash).context("failed to sign transaction")?;
+        let v = self.gas.chain_id * 2 + 35 + u64::from(recovery_id.to_byte());
+        let raw = self.rlp_encode(v, &signature.r().to_bytes(), &signature.s().to_bytes());
+        Ok(SubmittableTransaction {
+            hash: keccak256(&raw),
+            raw,
+        })
+    }
+}
+
+/// An inbound transfer verified against the Router's `InInstruction` event:
+/// the event is only trusted once it's cross-checked against the matching
+/// ERC-20/ETH transfer event in the same block, so a forged log alone can't
+/// fake a deposit.
+pub struct InInstruction {
+    /// Transaction that emitted the matching `InInstruction` and transfer events.
+    pub tx_hash: [u8; 32],
+    /// Token transferred; `None` for a native ETH transfer.
+    pub token: Option<[u8; 20]>,
+    /// Amount transferred, in the token's smallest unit.
+    pub amount: u128,
+    /// Opaque instruction payload emitted alongside the transfer.
+    pub instruction: Vec<u8>,
+}
+
+/// The Router's `InInstruction` event, decoded but not yet trusted.
+pub struct InInstructionEvent {
+    /// Transaction that emitted this event.
+    pub tx_hash: [u8; 32],
+    /// Token the event claims was transferred; `None` for native ETH.
+    pub token: Option<[u8; 20]>,
+    /// Amount the event claims was transferred.
+    pub amount: u128,
+    /// Opaque instruction payload.
+    pub instruction: Vec<u8>,
+}
+
+/// The ERC-20/ETH transfer event an [`InInstructionEvent`] must be paired
+/// with before it's trusted.
+pub struct TransferEvent {
+    /// Transaction that emitted this transfer.
+    pub tx_hash: [u8; 32],
+    /// Token transferred; `None` for native ETH.
+    pub token: Option<[u8; 20]>,
+    /// Amount transferred.
+    pub amount: u128,
+}
+
+impl InInstruction {
+    /// Cross-check `event` against the `transfer` in the same transaction,
+    /// rejecting it unless the transfer moves the same token and amount. A
+    /// Router contract only ever emits both in the same call, so a forged
+    /// `InInstruction` log without a matching transfer can't pass this.
+    pub fn verify(event: InInstructionEvent, transfer: &TransferEvent) -> Result<Self> {
+        anyhow::ensure!(
+            event.tx_hash == transfer.tx_hash,
+            "InInstruction and transfer event are not from the same transaction"
+        );
+        anyhow::ensure!(
+            event.token == transfer.token,
+            "InInstruction token does not match the transfer event's token"
+        );
+        anyhow::ensure!(
+            event.amount == transfer.amount,
+            "InInstruction amount does not match the transfer event's amount"
+        );
+        Ok(Self {
+            tx_hash: event.tx_hash,
+            token: event.token,
+            amount: event.amount,
+            instruction: event.instruction,
+        })
+    }
+}
+
+/// Router contract methods exposed through `/call`.
+enum RouterCall {
+    /// Rotate the threshold Schnorr key authorized to call `execute`.
+    UpdateKey {
+        /// Router contract address.
+        router: [u8; 20],
+        /// Relayer nonce to submit this call at.
+        nonce: u64,
+        /// New Schnorr public key.
+        new_key: Vec<u8>,
+    },
+    /// Execute a batch of outbound transfers, authorized by the current key.
+    Execute {
+        /// Router contract address.
+        router: [u8; 20],
+        /// Relayer nonce to submit this call at.
+        nonce: u64,
+        /// ABI-encoded calls to execute.
+        calls: Vec<u8>,
+        /// Schnorr signature authorizing this execution.
+        signature: Vec<u8>,
+    },
+}
+
+/// Dispatch a `/call` [`CallRequest`] for one of the `deployer.*`/`router.*`
+/// methods, signing and returning the submittable transaction as JSON.
+pub async fn handle_call_request(request: &CallRequest, deployer: &Deployer) -> Result<Value> {
+    match request.method.as_str() {
+        "deployer.deploy" => {
+            let tx = deployer.deploy_transaction()?;
+            Ok(submittable_response(&deployer.router_address(), &tx))
+        }
+        "router.updateKey" => {
+            let call = parse_update_key(request)?;
+            let RouterCall::UpdateKey { router, nonce, new_key } = call else {
+                unreachable!()
+            };
+            let data = encode_call("updateKey(bytes)", &[&new_key]);
+            let tx = deployer.call_transaction(router, nonce, data)?;
+            Ok(submittable_response(&router, &tx))
+        }
+        "router.execute" => {
+            let call = parse_execute(request)?;
+            let RouterCall::Execute { router, nonce, calls, signature } = call else {
+                unreachable!()
+            };
+            let data = encode_call("execute(bytes,bytes)", &[&calls, &signature]);
+            let tx = deployer.call_transaction(router, nonce, data)?;
+            Ok(submittable_response(&router, &tx))
+        }
+        method => anyhow::bail!("unsupported call method {method}"),
+    }
+}
+
+fn submittable_response(router: &[u8; 20], tx: &SubmittableTransaction) -> Value {
+    json!({
+        "router_address": hex(router),
+        "transaction": hex(&tx.raw),
+        "transaction_hash": hex(&tx.hash),
+    })
+}
+
+fn parse_update_key(request: &CallRequest) -> Result<RouterCall> {
+    let router = parse_address(&request.parameters, "router")?;
+    let nonce = parse_u64(&request.parameters, "nonce")?;
+    let new_key = parse_bytes(&request.parameters, "new_key")?;
+    Ok(RouterCall::UpdateKey { router, nonce, new_key })
+}
+
+fn parse_execute(request: &CallRequest) -> Result<RouterCall> {
+    let router = parse_address(&request.parameters, "router")?;
+    let nonce = parse_u64(&request.parameters, "nonce")?;
+    let calls = parse_bytes(&request.parameters, "calls")?;
+    let signature = parse_bytes(&request.parameters, "signature")?;
+    Ok(RouterCall::Execute {
+        router,
+        nonce,
+        calls,
+        signature,
+    })
+}
+
+fn parse_address(parameters: &Value, field: &str) -> Result<[u8; 20]> {
+    let bytes = parse_bytes(parameters, field)?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("{field} is not a 20-byte address"))
+}
+
+fn parse_u64(parameters: &Value, field: &str) -> Result<u64> {
+    parameters
+        .get(field)
+        .with_context(|| format!("missing {field}"))?
+        .as_u64()
+        .with_context(|| format!("{field} must be a non-negative integer"))
+}
+
+fn parse_bytes(parameters: &Value, field: &str) -> Result<Vec<u8>> {
+    let value = parameters
+        .get(field)
+        .with_context(|| format!("missing {field}"))?
+        .as_str()
+        .with_context(|| format!("{field} must be a hex string"))?;
+    hex::decode(value.trim_start_matches("0x")).with_context(|| format!("invalid hex in {field}"))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// ABI-encode a call to `signature` taking only `bytes` parameters: a
+/// 4-byte selector followed by one 32-byte offset per argument and each
+/// argument's length-prefixed, zero-padded contents, in order.
+fn encode_call(signature: &str, args: &[&[u8]]) -> Vec<u8> {
+    let mut data = keccak256(signature.as_bytes())[..4].to_vec();
+    let head_len = (args.len() * 32) as u64;
+    let mut offset = head_len;
+    let mut tails = Vec::new();
+    for arg in args {
+        data.extend_from_slice(&word(offset));
+        let mut tail = word(arg.len() as u64).to_vec();
+        tail.extend_from_slice(arg);
+        pad_to_word(&mut tail);
+        offset += tail.len() as u64;
+        tails.extend(tail);
+    }
+    data.extend(tails);
+    data
+}
+
+/// Right-pad `data` with zeros up to the next multiple of 32 bytes.
+fn pad_to_word(data: &mut Vec<u8>) {
+    let remainder = data.len() % 32;
+    if remainder != 0 {
+        data.resize(data.len() + (32 - remainder), 0);
+    }
+}
+
+/// A `u64` as a big-endian, left-padded 32-byte ABI word.
+fn word(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
@@ -0,0 +1,83 @@
+//! Minimal BIP-32 child key derivation for secp256k1.
+//!
+//! [`crate::mnemonic::Mnemonic`] normally signs with a single fixed key, but
+//! [`crate::BitcoinWallet`] needs to sign each PSBT input with the exact
+//! child key its descriptor derivation index expects. This gives it that one
+//! primitive without pulling in a full BIP-32 crate.
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use k256::ecdsa::SigningKey;
+use sha2::Sha512;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Derive the secp256k1 signing key at `path` (e.g. `"m/84'/0'/0'/0/3"`) from
+/// a BIP-39 seed, via standard BIP-32 `CKDpriv`.
+pub(crate) fn derive_secp256k1(seed: &[u8], path: &str) -> Result<SigningKey> {
+    let (mut key, mut chain_code) = master_key(seed)?;
+    for segment in path.trim_start_matches("m/").split('/') {
+        if segment.is_empty() || segment == "m" {
+            continue;
+        }
+        let hardened = segment.ends_with('\'');
+        let index: u32 = segment
+            .trim_end_matches('\'')
+            .parse()
+            .with_context(|| format!("invalid derivation path segment {segment}"))?;
+        let index = if hardened { index | 0x8000_0000 } else { index };
+        (key, chain_code) = child_key(&key, &chain_code, index)?;
+    }
+    Ok(key)
+}
+
+fn master_key(seed: &[u8]) -> Result<(SigningKey, [u8; 32])> {
+    let mut mac = HmacSha512::new_from_slice(b"Bitcoin seed").expect("hmac accepts any key length");
+    mac.update(seed);
+    let i = mac.finalize().into_bytes();
+    let key = SigningKey::from_bytes(i[..32].into()).context("invalid master key")?;
+    let mut chain_code = [0u8; 32];
+    chain_code.copy_from_slice(&i[32..]);
+    Ok((key, chain_code))
+}
+
+fn child_key(parent: &SigningKey, chain_code: &[u8; 32], index: u32) -> Result<(SigningKey, [u8; 32])> {
+    let mut mac = HmacSha512::new_from_slice(chain_code).expect("hmac accepts any key length");
+    if index & 0x8000_0000 != 0 {
+        mac.update(&[0u8]);
+        mac.update(&parent.to_bytes());
+    } else {
+        mac.update(parent.verifying_key().to_encoded_point(true).as_bytes());
+    }
+    mac.update(&index.to_be_bytes());
+    let i = mac.finalize().into_bytes();
+
+    let offset = k256::SecretKey::from_slice(&i[..32]).context("invalid child key offset")?;
+    let child_scalar = *parent.as_nonzero_scalar().as_ref() + *offset.to_nonzero_scalar().as_ref();
+    let child_key =
+        SigningKey::from_bytes(&child_scalar.to_bytes()).context("derived child key is invalid")?;
+
+    let mut child_chain_code = [0u8; 32];
+    child_chain_code.copy_from_slice(&i[32..]);
+    Ok((child_key, child_chain_code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_same_key_for_same_path() {
+        let seed = [7u8; 64];
+        let a = derive_secp256k1(&seed, "m/84'/0'/0'/0/0").unwrap();
+        let b = derive_secp256k1(&seed, "m/84'/0'/0'/0/0").unwrap();
+        assert_eq!(a.to_bytes(), b.to_bytes());
+    }
+
+    #[test]
+    fn derives_different_keys_for_different_index() {
+        let seed = [7u8; 64];
+        let a = derive_secp256k1(&seed, "m/84'/0'/0'/0/0").unwrap();
+        let b = derive_secp256k1(&seed, "m/84'/0'/0'/0/1").unwrap();
+        assert_ne!(a.to_bytes(), b.to_bytes());
+    }
+}
@@ -0,0 +1,51 @@
+//! Bitcoin-specific coin selection and fee-bump logic for [`crate::BitcoinWallet`].
+//!
+//! Bitcoin has no nonce to assign, so it can't go through the chain-agnostic
+//! [`crate::middleware`] stack or [`crate::tx_builder::UnsignedTransaction`]
+//! the way Ethereum/Polkadot sends do; this module is its analogue, built
+//! directly on BDK's own coin selection and `build_fee_bump` instead.
+use anyhow::Result;
+use bdk::bitcoin::psbt::PartiallySignedTransaction;
+use bdk::bitcoin::{Address, Txid};
+use bdk::database::MemoryDatabase;
+use bdk::{FeeRate, Wallet as BdkWallet};
+
+/// Builds spend and fee-bump PSBTs against a single [`BdkWallet`], leaving
+/// coin selection to BDK's own default selection algorithm and signing to
+/// [`crate::Signer`] (via [`crate::BitcoinWallet::sign_psbt`]).
+pub struct BitcoinTxBuilder<'a> {
+    wallet: &'a BdkWallet<MemoryDatabase>,
+}
+
+impl<'a> BitcoinTxBuilder<'a> {
+    /// Build against `wallet`'s current UTXO set.
+    pub fn new(wallet: &'a BdkWallet<MemoryDatabase>) -> Self {
+        Self { wallet }
+    }
+
+    /// Build (but don't sign) a PSBT spending to `recipients` at `fee_rate`,
+    /// selecting inputs with BDK's default coin selection and enabling RBF
+    /// so the result can later be fee-bumped with [`Self::bump_fee`].
+    pub fn build_transfer(
+        &self,
+        recipients: &[(Address, u64)],
+        fee_rate: FeeRate,
+    ) -> Result<PartiallySignedTransaction> {
+        let mut builder = self.wallet.build_tx();
+        for (address, amount) in recipients {
+            builder.add_recipient(address.script_pubkey(), *amount);
+        }
+        builder.fee_rate(fee_rate).enable_rbf();
+        let (psbt, _details) = builder.finish()?;
+        Ok(psbt)
+    }
+
+    /// Build a replacement PSBT for the already-broadcast, RBF-enabled
+    /// transaction `txid`, bumping its fee to `new_fee_rate`.
+    pub fn bump_fee(&self, txid: Txid, new_fee_rate: FeeRate) -> Result<PartiallySignedTransaction> {
+        let mut builder = self.wallet.build_fee_bump(txid)?;
+        builder.fee_rate(new_fee_rate).enable_rbf();
+        let (psbt, _details) = builder.finish()?;
+        Ok(psbt)
+    }
+}
@@ -0,0 +1,78 @@
+//! The base (terminal) [`crate::middleware::Middleware`] layer: talks
+//! directly to a connector implementing [`rosetta_core::BlockchainClient`],
+//! so every wrapping layer (nonce manager, fee oracle, signer) can stay
+//! connector-agnostic.
+use crate::middleware::Middleware;
+use crate::signer::Curve;
+use crate::tx_builder::UnsignedTransaction;
+use async_trait::async_trait;
+use rosetta_core::types::TransactionIdentifier;
+use rosetta_core::BlockchainClient;
+use std::sync::Arc;
+
+/// Error surfaced by the base connector layer: any failure talking to the
+/// connector (RPC, serialization, chain-specific) collapses to this, so
+/// every wrapping layer can stay generic over `Error`.
+#[derive(Debug)]
+pub struct ClientError(anyhow::Error);
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<anyhow::Error> for ClientError {
+    fn from(err: anyhow::Error) -> Self {
+        Self(err)
+    }
+}
+
+/// Adapts any connector into the base layer of a [`Middleware`] stack: it
+/// has no further inner layer, and every method talks to the connector
+/// directly instead of delegating.
+#[async_trait]
+impl<C: BlockchainClient> Middleware for Arc<C> {
+    type Inner = Self;
+    type Error = ClientError;
+
+    fn inner(&self) -> &Self::Inner {
+        self
+    }
+
+    fn curve(&self) -> Curve {
+        Curve::for_blockchain(&self.config().blockchain)
+    }
+
+    async fn next_nonce(&self, from: &str) -> Result<u64, Self::Error> {
+        Ok(self.account(from).await.map_err(anyhow::Error::from)?.nonce)
+    }
+
+    async fn estimate_fee(&self, tx: &UnsignedTransaction) -> Result<u128, Self::Error> {
+        Ok(self
+            .fee_estimate(tx.from(), tx.to(), tx.amount())
+            .await
+            .map_err(anyhow::Error::from)?)
+    }
+
+    async fn fill_transaction(&self, _tx: &mut UnsignedTransaction) -> Result<(), Self::Error> {
+        // Nonce and fee are filled in by the layers above; the base layer
+        // has nothing left to contribute.
+        Ok(())
+    }
+
+    async fn send_transaction(
+        &self,
+        tx: UnsignedTransaction,
+    ) -> Result<TransactionIdentifier, Self::Error> {
+        let signature = tx
+            .signature()
+            .ok_or_else(|| anyhow::anyhow!("transaction must be signed before submission"))?;
+        Ok(self
+            .submit(tx.from(), tx.to(), tx.amount(), signature)
+            .await
+            .map_err(anyhow::Error::from)?)
+    }
+}
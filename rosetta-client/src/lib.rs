@@ -5,16 +5,29 @@ use anyhow::Result;
 pub use crate::wallet::Wallet;
 pub use rosetta_core::{crypto, types, BlockchainConfig};
 
+mod bip32;
+mod bitcoin_tx_builder;
+mod bitcoin_wallet;
 mod client;
+mod middleware;
 mod mnemonic;
+mod registry;
 mod signer;
 mod tx_builder;
+mod tx_pool;
 mod wallet;
 
+pub use bitcoin_wallet::BitcoinWallet;
+pub use middleware::{GasOracle, Middleware, NonceManager, SignerMiddleware};
+pub use registry::{register_chain, ChainRegistration, ClientFactory};
 pub use signer::Signer;
+pub use tx_pool::{DropReason, PoolListener, PooledTransaction, TxPool, TxPoolConfig};
 
-/// Supported chains.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Supported chains. The built-in variants are resolved through the chain
+/// registry rather than hard-coded here; `Other` lets callers name a chain
+/// registered at runtime via [`register_chain`] without editing this enum.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum Blockchain {
     /// Bitcoin
     Bitcoin,
@@ -24,6 +37,34 @@ pub enum Blockchain {
     Astar,
     /// Polkadot
     Polkadot,
+    /// Any other chain registered with [`register_chain`], named as given.
+    Other(String),
+}
+
+impl Blockchain {
+    /// The registry name used to resolve this chain's [`BlockchainConfig`].
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Bitcoin => "bitcoin",
+            Self::Ethereum => "ethereum",
+            Self::Astar => "astar",
+            Self::Polkadot => "polkadot",
+            Self::Other(name) => name,
+        }
+    }
+
+    /// Build this chain's [`BlockchainConfig`] for `network` by dispatching
+    /// through the chain registry.
+    pub fn config(&self, network: &str) -> Result<BlockchainConfig> {
+        registry::resolve(self.as_str(), network)
+    }
+
+    /// This chain's registered [`ClientFactory`], if it has one. `None` for
+    /// every chain built into this crate; see [`ChainRegistration::client`].
+    pub fn client_factory(&self) -> Result<Option<ClientFactory>> {
+        registry::client_factory(self.as_str())
+    }
 }
 
 impl std::str::FromStr for Blockchain {
@@ -35,6 +76,7 @@ impl std::str::FromStr for Blockchain {
             "ethereum" => Self::Ethereum,
             "astar" => Self::Astar,
             "polkadot" => Self::Polkadot,
+            name if registry::is_registered(name) => Self::Other(name.to_string()),
             _ => anyhow::bail!("unsupported blockchain {}", blockchain),
         })
     }
@@ -0,0 +1,267 @@
+//! Composable middleware stack for [`crate::Wallet`], modeled on ethers-rs's
+//! `Middleware` trait: each layer wraps an inner layer and only overrides the
+//! methods it actually changes, delegating everything else by default.
+use crate::tx_builder::UnsignedTransaction;
+use anyhow::Result;
+use async_trait::async_trait;
+use rosetta_core::types::TransactionIdentifier;
+
+/// A single layer in the client middleware stack.
+///
+/// Implementors wrap an [`Middleware::Inner`] layer and delegate to it by
+/// default, so a layer only has to override the handful of methods it cares
+/// about. The associated `Error` type lets each stack stay generic across
+/// chains that surface different JSON-RPC error shapes.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// The next layer down the stack.
+    type Inner: Middleware<Error = Self::Error>;
+    /// Error type shared by every layer in this stack.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Access the inner layer.
+    fn inner(&self) -> &Self::Inner;
+
+    /// The curve transactions in this stack must be signed with. Only the
+    /// base layer (the one actually talking to the connector) knows this;
+    /// every other layer delegates to it by default.
+    fn curve(&self) -> crate::signer::Curve {
+        self.inner().curve()
+    }
+
+    /// Resolve the nonce to use for `from`'s next transaction.
+    async fn next_nonce(&self, from: &str) -> Result<u64, Self::Error> {
+        self.inner().next_nonce(from).await
+    }
+
+    /// Estimate a fee/gas price for `tx`.
+    async fn estimate_fee(&self, tx: &UnsignedTransaction) -> Result<u128, Self::Error> {
+        self.inner().estimate_fee(tx).await
+    }
+
+    /// Fill in any fields `tx` is missing (nonce, fee) before signing.
+    async fn fill_transaction(&self, tx: &mut UnsignedTransaction) -> Result<(), Self::Error> {
+        self.inner().fill_transaction(tx).await
+    }
+
+    /// Fill in this layer's own fields, then sign and submit `tx`, returning
+    /// its identifier. The default fills via [`Self::fill_transaction`]
+    /// before delegating, so every layer in the stack gets a chance to
+    /// contribute regardless of where a caller entered it; a layer only
+    /// overrides this to change how submission itself works (e.g.
+    /// [`SignerMiddleware`] signing before handing off).
+    async fn send_transaction(
+        &self,
+        mut tx: UnsignedTransaction,
+    ) -> Result<TransactionIdentifier, Self::Error> {
+        self.fill_transaction(&mut tx).await?;
+        self.inner().send_transaction(tx).await
+    }
+}
+
+/// Assigns the next sequential nonce to every outgoing transaction, tracking
+/// the last nonce it handed out per sender so callers never have to manage
+/// nonces themselves.
+pub struct NonceManager<M> {
+    inner: M,
+    nonces: tokio::sync::Mutex<std::collections::HashMap<String, u64>>,
+}
+
+impl<M> NonceManager<M> {
+    /// Wrap `inner` with automatic nonce assignment.
+    pub fn new(inner: M) -> Self {
+        Self {
+            inner,
+            nonces: tokio::sync::Mutex::new(std::collections::HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl<M: Middleware + Sync> Middleware for NonceManager<M> {
+    type Inner = M;
+    type Error = M::Error;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn fill_transaction(&self, tx: &mut UnsignedTransaction) -> Result<(), Self::Error> {
+        if tx.nonce().is_none() {
+            let mut nonces = self.nonces.lock().await;
+            let nonce = match nonces.get(tx.from()) {
+                Some(nonce) => nonce + 1,
+                None => self.inner.next_nonce(tx.from()).await?,
+            };
+            nonces.insert(tx.from().to_string(), nonce);
+            tx.set_nonce(nonce);
+        }
+        self.inner.fill_transaction(tx).await
+    }
+}
+
+/// Fills in a fee/gas price estimate for transactions that don't already
+/// carry one, so callers don't have to query the connector themselves.
+pub struct GasOracle<M> {
+    inner: M,
+}
+
+impl<M> GasOracle<M> {
+    /// Wrap `inner` with automatic fee estimation.
+    pub fn new(inner: M) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl<M: Middleware + Sync> Middleware for GasOracle<M> {
+    type Inner = M;
+    type Error = M::Error;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn fill_transaction(&self, tx: &mut UnsignedTransaction) -> Result<(), Self::Error> {
+        if tx.fee().is_none() {
+            let fee = self.inner.estimate_fee(tx).await?;
+            tx.set_fee(fee);
+        }
+        self.inner.fill_transaction(tx).await
+    }
+}
+
+/// Signs filled-in transactions with a [`crate::Signer`] before submitting
+/// them to the inner layer.
+pub struct SignerMiddleware<M> {
+    inner: M,
+    signer: crate::Signer,
+}
+
+impl<M> SignerMiddleware<M> {
+    /// Wrap `inner`, signing every outgoing transaction with `signer`.
+    pub fn new(inner: M, signer: crate::Signer) -> Self {
+        Self { inner, signer }
+    }
+}
+
+#[async_trait]
+impl<M: Middleware + Sync> Middleware for SignerMiddleware<M>
+where
+    M::Error: From<anyhow::Error>,
+{
+    type Inner = M;
+    type Error = M::Error;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn send_transaction(
+        &self,
+        tx: UnsignedTransaction,
+    ) -> Result<TransactionIdentifier, Self::Error> {
+        // By the time a send reaches this layer, the outer stack (nonce
+        // manager, gas oracle) has already filled `tx` in via the trait
+        // default's fill-then-delegate `send_transaction`; only signing is
+        // this layer's own job.
+        let signed = tx.sign(&self.signer)?;
+        self.inner.send_transaction(signed).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ClientError;
+    use crate::signer::Curve;
+    use rosetta_core::types::TransactionIdentifier;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// A fake base layer that hands out sequential nonces (one counter
+    /// shared by every sender) and a fixed fee, recording every submitted transaction instead of talking
+    /// to a real connector.
+    struct MockBase {
+        next_nonce: AtomicU64,
+        submitted: tokio::sync::Mutex<Vec<UnsignedTransaction>>,
+    }
+
+    impl MockBase {
+        fn new() -> Self {
+            Self {
+                next_nonce: AtomicU64::new(0),
+                submitted: tokio::sync::Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Middleware for MockBase {
+        type Inner = Self;
+        type Error = ClientError;
+
+        fn inner(&self) -> &Self::Inner {
+            self
+        }
+
+        fn curve(&self) -> Curve {
+            Curve::Secp256k1
+        }
+
+        async fn next_nonce(&self, _from: &str) -> Result<u64, ClientError> {
+            Ok(self.next_nonce.fetch_add(1, Ordering::SeqCst))
+        }
+
+        async fn estimate_fee(&self, _tx: &UnsignedTransaction) -> Result<u128, ClientError> {
+            Ok(100)
+        }
+
+        async fn fill_transaction(&self, _tx: &mut UnsignedTransaction) -> Result<(), ClientError> {
+            Ok(())
+        }
+
+        async fn send_transaction(
+            &self,
+            tx: UnsignedTransaction,
+        ) -> Result<TransactionIdentifier, ClientError> {
+            self.submitted.lock().await.push(tx);
+            Ok(TransactionIdentifier {
+                hash: "0xmock".to_string(),
+            })
+        }
+    }
+
+    fn stack(base: MockBase) -> NonceManager<GasOracle<SignerMiddleware<MockBase>>> {
+        let signer = crate::Signer::generate().unwrap();
+        NonceManager::new(GasOracle::new(SignerMiddleware::new(base, signer)))
+    }
+
+    #[tokio::test]
+    async fn assigns_sequential_nonces_per_sender() {
+        let stack = stack(MockBase::new());
+        for expected in 0..3 {
+            let tx = UnsignedTransaction::new("alice", "bob", 1, Curve::Secp256k1);
+            stack.send_transaction(tx).await.unwrap();
+            let submitted = stack.inner().inner().inner.submitted.lock().await;
+            assert_eq!(submitted.last().unwrap().nonce(), Some(expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn fills_in_fee_when_missing() {
+        let stack = stack(MockBase::new());
+        let tx = UnsignedTransaction::new("alice", "bob", 1, Curve::Secp256k1);
+        stack.send_transaction(tx).await.unwrap();
+        let submitted = stack.inner().inner().inner.submitted.lock().await;
+        assert_eq!(submitted.last().unwrap().fee(), Some(100));
+    }
+
+    #[tokio::test]
+    async fn signs_before_submitting() {
+        let stack = stack(MockBase::new());
+        let tx = UnsignedTransaction::new("alice", "bob", 1, Curve::Secp256k1);
+        stack.send_transaction(tx).await.unwrap();
+        let submitted = stack.inner().inner().inner.submitted.lock().await;
+        assert!(submitted.last().unwrap().signature().is_some());
+    }
+}
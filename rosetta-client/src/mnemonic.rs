@@ -0,0 +1,74 @@
+//! BIP-39 mnemonic handling and per-curve signing for [`crate::signer::Signer`].
+use crate::signer::Curve;
+use anyhow::{Context, Result};
+use bip39::{Language, Mnemonic as Bip39Mnemonic};
+
+/// A BIP-39 mnemonic phrase, the root of every key a [`crate::Signer`]
+/// derives in software (i.e. everywhere except the `ledger` feature).
+#[derive(Clone)]
+pub struct Mnemonic {
+    phrase: String,
+}
+
+impl Mnemonic {
+    /// Generate a fresh 24-word mnemonic.
+    pub fn generate() -> Result<Self> {
+        let mnemonic =
+            Bip39Mnemonic::generate_in(Language::English, 24).context("failed to generate mnemonic")?;
+        Ok(Self {
+            phrase: mnemonic.to_string(),
+        })
+    }
+
+    /// Restore from an existing phrase, validating it first.
+    pub fn from_phrase(phrase: &str) -> Result<Self> {
+        Bip39Mnemonic::parse_in(Language::English, phrase).context("invalid mnemonic phrase")?;
+        Ok(Self {
+            phrase: phrase.to_string(),
+        })
+    }
+
+    /// The phrase, for export/backup.
+    #[must_use]
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    /// Derive this mnemonic's key for `curve` and sign `message` with it.
+    pub fn sign(&self, curve: Curve, message: &[u8]) -> Result<Vec<u8>> {
+        let seed = Bip39Mnemonic::parse_in(Language::English, &self.phrase)?.to_seed("");
+        match curve {
+            Curve::Secp256k1 => sign_secp256k1(&seed, message),
+            Curve::Sr25519 => sign_sr25519(&seed, message),
+        }
+    }
+
+    /// Derive the secp256k1 key at `derivation_path` (unlike [`Self::sign`],
+    /// which always signs with the same fixed key) and DER-sign `message`
+    /// with it. `message` is a 32-byte hash and is signed as is, without
+    /// hashing it again. Used by [`crate::BitcoinWallet::sign_psbt`] to sign
+    /// each PSBT input's sighash with the exact child key its descriptor
+    /// derivation index expects.
+    pub fn sign_at(&self, derivation_path: &str, message: &[u8]) -> Result<Vec<u8>> {
+        use k256::ecdsa::{signature::hazmat::PrehashSigner as _, Signature};
+        let seed = Bip39Mnemonic::parse_in(Language::English, &self.phrase)?.to_seed("");
+        let key = crate::bip32::derive_secp256k1(seed.as_bytes(), derivation_path)?;
+        let signature: Signature = key.sign_prehash(message).context("failed to sign hash")?;
+        Ok(signature.to_der().to_bytes().to_vec())
+    }
+}
+
+fn sign_secp256k1(seed: &[u8; 64], message: &[u8]) -> Result<Vec<u8>> {
+    use k256::ecdsa::{signature::Signer as _, Signature, SigningKey};
+    let key = SigningKey::from_bytes(seed[..32].into()).context("invalid secp256k1 seed")?;
+    let signature: Signature = key.sign(message);
+    Ok(signature.to_vec())
+}
+
+fn sign_sr25519(seed: &[u8; 64], message: &[u8]) -> Result<Vec<u8>> {
+    use schnorrkel::{Keypair, MiniSecretKey};
+    let mini = MiniSecretKey::from_bytes(&seed[..32]).map_err(|err| anyhow::anyhow!("{err}"))?;
+    let keypair: Keypair = mini.expand_to_keypair(MiniSecretKey::ED25519_MODE);
+    let context = schnorrkel::signing_context(b"substrate");
+    Ok(keypair.sign(context.bytes(message)).to_bytes().to_vec())
+}
@@ -0,0 +1,112 @@
+//! Runtime registry of known chains, so adding a chain is a registration
+//! call rather than an edit to this crate. Borrows graph-node's approach of
+//! treating chain identity as data: a chain is a name resolved against a map,
+//! not a hard-coded variant.
+use anyhow::{Context, Result};
+use rosetta_core::{BlockchainClient, BlockchainConfig};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Builds the [`BlockchainConfig`] for one network of a registered chain.
+pub type ConfigFactory = fn(network: &str) -> Result<BlockchainConfig>;
+
+/// Builds a connector for a registered chain, type-erased (like
+/// [`ConfigFactory`] builds a config) so the registry can hand one back for
+/// a chain it only knows by name.
+pub type ClientFactory =
+    fn(config: BlockchainConfig) -> Pin<Box<dyn Future<Output = Result<Arc<dyn BlockchainClient>>> + Send>>;
+
+/// A chain known to the registry: its canonical name, how to build a
+/// [`BlockchainConfig`] for one of its networks (e.g. "mainnet", "testnet"),
+/// and, optionally, how to build a connector for it.
+#[derive(Clone, Copy)]
+pub struct ChainRegistration {
+    /// Canonical name used to look the chain up, e.g. `"bitcoin"`.
+    pub name: &'static str,
+    /// Builds the config for a given network of this chain.
+    pub config: ConfigFactory,
+    /// Builds a connector for this chain, so callers can drive it by name
+    /// alone instead of supplying a `T: BlockchainClient` themselves (e.g.
+    /// to `rosetta_docker::Env::new`).
+    ///
+    /// `None` for the chains built into this crate below: their connectors
+    /// live in `rosetta-server-*` crates, which this crate deliberately
+    /// doesn't depend on so that using `rosetta-client` doesn't pull in
+    /// every chain's node/RPC stack. A chain registered via
+    /// [`register_chain`] with a `client` factory can be driven end-to-end
+    /// by name; the built-ins still need their connector supplied at the
+    /// call site until something sits above both crates to wire it.
+    pub client: Option<ClientFactory>,
+}
+
+fn registry() -> &'static Mutex<HashMap<&'static str, ChainRegistration>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<&'static str, ChainRegistration>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(built_in_chains()))
+}
+
+fn built_in_chains() -> HashMap<&'static str, ChainRegistration> {
+    let mut chains = HashMap::new();
+    for registration in [
+        ChainRegistration {
+            name: "bitcoin",
+            config: rosetta_config_bitcoin::config,
+            client: None,
+        },
+        ChainRegistration {
+            name: "ethereum",
+            config: rosetta_config_ethereum::config,
+            client: None,
+        },
+        ChainRegistration {
+            name: "astar",
+            config: rosetta_config_astar::config,
+            client: None,
+        },
+        ChainRegistration {
+            name: "polkadot",
+            config: rosetta_config_polkadot::config,
+            client: None,
+        },
+    ] {
+        chains.insert(registration.name, registration);
+    }
+    chains
+}
+
+/// Register a chain so it can be resolved by name. Downstream crates call
+/// this (typically once, at startup) to teach the registry about a chain
+/// this crate doesn't know about natively.
+pub fn register_chain(registration: ChainRegistration) {
+    registry()
+        .lock()
+        .unwrap()
+        .insert(registration.name, registration);
+}
+
+/// Whether `name` has a registered chain.
+pub fn is_registered(name: &str) -> bool {
+    registry().lock().unwrap().contains_key(name)
+}
+
+/// Resolve `"<chain>"` against the registry and build its [`BlockchainConfig`]
+/// for `network`.
+pub fn resolve(name: &str, network: &str) -> Result<BlockchainConfig> {
+    let registration = *registry()
+        .lock()
+        .unwrap()
+        .get(name)
+        .with_context(|| format!("unsupported blockchain {name}"))?;
+    (registration.config)(network)
+}
+
+/// Resolve `name`'s [`ClientFactory`], if the chain registered one.
+pub fn client_factory(name: &str) -> Result<Option<ClientFactory>> {
+    let registration = *registry()
+        .lock()
+        .unwrap()
+        .get(name)
+        .with_context(|| format!("unsupported blockchain {name}"))?;
+    Ok(registration.client)
+}
@@ -0,0 +1,340 @@
+//! Key management for [`crate::Wallet`].
+use crate::mnemonic::Mnemonic;
+use anyhow::Result;
+
+/// Curve used to derive keys and sign transactions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Curve {
+    /// secp256k1, used by Bitcoin and Ethereum.
+    Secp256k1,
+    /// sr25519, used by Polkadot/Substrate chains.
+    Sr25519,
+}
+
+impl Curve {
+    /// The curve accounts on `blockchain` (a [`crate::registry`] name) sign
+    /// with, used by the base [`crate::middleware::Middleware`] layer so
+    /// [`crate::Wallet`] doesn't have to be told the curve separately.
+    #[must_use]
+    pub(crate) fn for_blockchain(blockchain: &str) -> Self {
+        match blockchain {
+            "polkadot" | "astar" => Self::Sr25519,
+            _ => Self::Secp256k1,
+        }
+    }
+}
+
+/// Problems specific to signing with a Ledger hardware wallet, kept distinct
+/// from key-derivation errors so [`crate::Wallet`] can prompt the user
+/// appropriately (e.g. "unlock your device" vs. "open the Ethereum app").
+#[derive(Debug)]
+pub enum LedgerError {
+    /// The device is connected but locked with a PIN.
+    Locked,
+    /// The device is unlocked but the chain-specific app isn't open.
+    AppNotOpen,
+    /// The user declined the signing request on the device itself.
+    UserRejected,
+    /// No Ledger device could be found over USB/HID.
+    NotConnected,
+    /// Any other transport- or protocol-level failure.
+    Other(anyhow::Error),
+}
+
+impl std::fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Locked => write!(f, "ledger device is locked"),
+            Self::AppNotOpen => write!(f, "open the chain app on the ledger device"),
+            Self::UserRejected => write!(f, "signing request was rejected on the ledger device"),
+            Self::NotConnected => write!(f, "no ledger device found"),
+            Self::Other(err) => write!(f, "ledger error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for LedgerError {}
+
+/// A key held in memory, generated locally or restored from a mnemonic.
+#[derive(Clone)]
+struct SoftwareSigner {
+    mnemonic: Mnemonic,
+}
+
+/// A key that never leaves a Ledger hardware device; every signature is
+/// produced by the device itself over USB/HID. Only the derivation path is
+/// kept between signatures — the USB/HID transport is opened fresh for each
+/// [`Signer::sign`] call, so a `Signer` stays cheap to clone and doesn't pin
+/// the device handle for its whole lifetime.
+#[cfg(feature = "ledger")]
+#[derive(Clone)]
+struct LedgerSigner {
+    derivation_path: String,
+}
+
+#[cfg(feature = "ledger")]
+impl LedgerSigner {
+    fn connect(derivation_path: &str) -> Result<Self, LedgerError> {
+        // Probe for a device up front so construction fails fast, even
+        // though the transport itself is reopened per signature.
+        let _ = Self::transport()?;
+        Ok(Self {
+            derivation_path: derivation_path.to_string(),
+        })
+    }
+
+    fn transport() -> Result<ledger_transport_hid::TransportNativeHID, LedgerError> {
+        let api = ledger_transport_hid::hidapi::HidApi::new()
+            .map_err(|err| LedgerError::Other(err.into()))?;
+        ledger_transport_hid::TransportNativeHID::new(&api).map_err(|_| LedgerError::NotConnected)
+    }
+}
+
+#[derive(Clone)]
+enum SignerInner {
+    Software(SoftwareSigner),
+    #[cfg(feature = "ledger")]
+    Ledger(LedgerSigner),
+}
+
+/// Signs transactions for a [`crate::Wallet`], either with an in-memory key
+/// or, behind the `ledger` feature, a connected Ledger hardware device.
+/// Cheap to clone: a [`crate::Wallet`] keeps its own copy rather than
+/// borrowing the caller's.
+#[derive(Clone)]
+pub struct Signer(SignerInner);
+
+impl Signer {
+    /// Generate a new software signer from a fresh random mnemonic.
+    pub fn generate() -> Result<Self> {
+        Ok(Self(SignerInner::Software(SoftwareSigner {
+            mnemonic: Mnemonic::generate()?,
+        })))
+    }
+
+    /// Restore a software signer from an existing mnemonic phrase.
+    pub fn from_mnemonic(phrase: &str) -> Result<Self> {
+        Ok(Self(SignerInner::Software(SoftwareSigner {
+            mnemonic: Mnemonic::from_phrase(phrase)?,
+        })))
+    }
+
+    /// Derive addresses and sign with a Ledger device at `derivation_path`,
+    /// instead of holding key material in memory. Mirrors [`Signer::generate`]
+    /// in that the result is usable anywhere a `Wallet` expects a `Signer`.
+    ///
+    /// Only available when the `ledger` feature is enabled, so the
+    /// pure-software build carries no USB/HID dependency.
+    #[cfg(feature = "ledger")]
+    pub fn ledger(derivation_path: &str) -> Result<Self, LedgerError> {
+        Ok(Self(SignerInner::Ledger(LedgerSigner::connect(
+            derivation_path,
+        )?)))
+    }
+
+    /// Sign `message` on `curve`, dispatching to the in-memory key or the
+    /// connected Ledger device.
+    pub fn sign(&self, curve: Curve, message: &[u8]) -> Result<Vec<u8>> {
+        match &self.0 {
+            SignerInner::Software(signer) => signer.mnemonic.sign(curve, message),
+            #[cfg(feature = "ledger")]
+            SignerInner::Ledger(signer) => {
+                ledger_sign(signer, &signer.derivation_path, curve, message).map_err(anyhow::Error::from)
+            }
+        }
+    }
+
+    /// Sign `message` with the key at `derivation_path` instead of this
+    /// signer's own fixed path. Only secp256k1 is supported. A software key
+    /// signs `message` as a 32-byte hash, without hashing it again. Lets a caller
+    /// managing several derived addresses (e.g. [`crate::BitcoinWallet`])
+    /// sign with the exact child key each address expects.
+    pub fn sign_at(&self, derivation_path: &str, message: &[u8]) -> Result<Vec<u8>> {
+        match &self.0 {
+            SignerInner::Software(signer) => signer.mnemonic.sign_at(derivation_path, message),
+            #[cfg(feature = "ledger")]
+            SignerInner::Ledger(signer) => {
+                ledger_sign(signer, derivation_path, Curve::Secp256k1, message).map_err(anyhow::Error::from)
+            }
+        }
+    }
+}
+
+/// Which Ledger app a derivation path routes to, and the APDU parameters it
+/// expects. Bitcoin and Ethereum both sign on [`Curve::Secp256k1`], so the
+/// path's BIP-44 coin type (not the curve) picks the app.
+#[cfg(feature = "ledger")]
+#[derive(Clone, Copy, Debug)]
+enum LedgerApp {
+    /// Coin type `60'`.
+    Ethereum,
+    /// Coin type `0'` (mainnet) or `1'` (testnet).
+    Bitcoin,
+}
+
+#[cfg(feature = "ledger")]
+impl LedgerApp {
+    const CLA_ETHEREUM: u8 = 0xE0;
+    const CLA_BITCOIN: u8 = 0xE0;
+    const INS_SIGN: u8 = 0x04;
+
+    /// Pick the app a BIP-44 `derivation_path` (e.g. `m/44'/60'/0'/0/0`)
+    /// routes to, based on its coin-type component.
+    fn for_derivation_path(derivation_path: &str) -> Result<Self, LedgerError> {
+        let coin_type = derivation_path
+            .trim_start_matches("m/")
+            .split('/')
+            .nth(1)
+            .and_then(|segment| segment.trim_end_matches('\'').parse::<u32>().ok())
+            .ok_or_else(|| {
+                LedgerError::Other(anyhow::anyhow!(
+                    "malformed derivation path {derivation_path}"
+                ))
+            })?;
+        match coin_type {
+            60 => Ok(Self::Ethereum),
+            0 | 1 => Ok(Self::Bitcoin),
+            other => Err(LedgerError::Other(anyhow::anyhow!(
+                "derivation path coin type {other}' has no known ledger app"
+            ))),
+        }
+    }
+
+    fn cla(self) -> u8 {
+        match self {
+            Self::Ethereum => Self::CLA_ETHEREUM,
+            Self::Bitcoin => Self::CLA_BITCOIN,
+        }
+    }
+
+    /// BIP-32 path as the raw `[u8; 4]`-per-index payload every Ledger app
+    /// expects ahead of the message to sign.
+    fn encode_path(derivation_path: &str) -> Vec<u8> {
+        let indices: Vec<u32> = derivation_path
+            .trim_start_matches("m/")
+            .split('/')
+            .filter_map(|segment| {
+                let hardened = segment.ends_with('\'');
+                segment
+                    .trim_end_matches('\'')
+                    .parse::<u32>()
+                    .ok()
+                    .map(|index| if hardened { index | 0x8000_0000 } else { index })
+            })
+            .collect();
+        let mut encoded = vec![indices.len() as u8];
+        for index in indices {
+            encoded.extend_from_slice(&index.to_be_bytes());
+        }
+        encoded
+    }
+}
+
+/// Send `message` to `app` over `transport` at `derivation_path`, signed by
+/// the key that path derives, and classify the device's response.
+///
+/// `derivation_path` is taken explicitly (rather than always reading
+/// `signer.derivation_path`) so [`Signer::sign_at`] can sign at a path other
+/// than the one the `Signer` was constructed with.
+#[cfg(feature = "ledger")]
+fn ledger_sign(
+    signer: &LedgerSigner,
+    derivation_path: &str,
+    curve: Curve,
+    message: &[u8],
+) -> Result<Vec<u8>, LedgerError> {
+    if curve != Curve::Secp256k1 {
+        return Err(LedgerError::Other(anyhow::anyhow!(
+            "ledger signing only supports the secp256k1 curve (Bitcoin/Ethereum), got {curve:?}"
+        )));
+    }
+
+    let transport = LedgerSigner::transport()?;
+    let app = LedgerApp::for_derivation_path(derivation_path)?;
+
+    let mut data = LedgerApp::encode_path(derivation_path);
+    data.extend_from_slice(message);
+
+    let command = ledger_apdu::APDUCommand {
+        cla: app.cla(),
+        ins: LedgerApp::INS_SIGN,
+        p1: 0x00,
+        p2: 0x00,
+        data,
+    };
+    let response = transport
+        .exchange(&command)
+        .map_err(|err| LedgerError::Other(err.into()))?;
+    classify_response(response.retcode(), response.apdu_data())
+}
+
+/// Map a device status word (and, on success, its payload) to either the
+/// signature bytes or a distinct, promptable [`LedgerError`].
+#[cfg(feature = "ledger")]
+fn classify_response(status_word: u16, data: &[u8]) -> Result<Vec<u8>, LedgerError> {
+    match status_word {
+        0x9000 => Ok(data.to_vec()),
+        // "Security status not satisfied": the device is locked.
+        0x6982 => Err(LedgerError::Locked),
+        // "Conditions of use not satisfied": the user declined on-device.
+        0x6985 => Err(LedgerError::UserRejected),
+        // Class/instruction not recognized: the expected app isn't open.
+        0x6d00 | 0x6e00 | 0x6a15 => Err(LedgerError::AppNotOpen),
+        other => Err(LedgerError::Other(anyhow::anyhow!(
+            "unexpected ledger status word {other:#06x}"
+        ))),
+    }
+}
+
+#[cfg(all(test, feature = "ledger"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_ethereum_path_to_ethereum_app() {
+        assert!(matches!(
+            LedgerApp::for_derivation_path("m/44'/60'/0'/0/0").unwrap(),
+            LedgerApp::Ethereum
+        ));
+    }
+
+    #[test]
+    fn routes_bitcoin_path_to_bitcoin_app() {
+        assert!(matches!(
+            LedgerApp::for_derivation_path("m/44'/0'/0'/0/0").unwrap(),
+            LedgerApp::Bitcoin
+        ));
+        assert!(matches!(
+            LedgerApp::for_derivation_path("m/84'/1'/0'/0/0").unwrap(),
+            LedgerApp::Bitcoin
+        ));
+    }
+
+    #[test]
+    fn unknown_coin_type_has_no_app() {
+        assert!(LedgerApp::for_derivation_path("m/44'/999'/0'/0/0").is_err());
+    }
+
+    #[test]
+    fn encodes_hardened_and_plain_indices() {
+        let encoded = LedgerApp::encode_path("m/44'/60'/0'/0/0");
+        assert_eq!(encoded[0], 5); // five path components
+        assert_eq!(&encoded[1..5], &(44 | 0x8000_0000u32).to_be_bytes());
+        assert_eq!(&encoded[17..21], &0u32.to_be_bytes()); // last (non-hardened) index
+    }
+
+    #[test]
+    fn classifies_known_status_words_distinctly() {
+        assert!(classify_response(0x9000, b"sig").unwrap() == b"sig");
+        assert!(matches!(classify_response(0x6982, &[]), Err(LedgerError::Locked)));
+        assert!(matches!(
+            classify_response(0x6985, &[]),
+            Err(LedgerError::UserRejected)
+        ));
+        assert!(matches!(
+            classify_response(0x6e00, &[]),
+            Err(LedgerError::AppNotOpen)
+        ));
+        assert!(matches!(classify_response(0x6f00, &[]), Err(LedgerError::Other(_))));
+    }
+}
@@ -0,0 +1,101 @@
+//! Chain-agnostic unsigned transaction, threaded through the
+//! [`crate::middleware`] stack and finalized by [`crate::Signer`].
+use crate::signer::{Curve, Signer};
+
+/// A transaction under construction: built with [`UnsignedTransaction::new`],
+/// filled in by the middleware stack (nonce, fee), then handed to
+/// [`UnsignedTransaction::sign`] before being submitted.
+#[derive(Clone, Debug)]
+pub struct UnsignedTransaction {
+    from: String,
+    to: String,
+    amount: u128,
+    curve: Curve,
+    nonce: Option<u64>,
+    fee: Option<u128>,
+    signature: Option<Vec<u8>>,
+}
+
+impl UnsignedTransaction {
+    /// Start building a transfer of `amount` from `from` to `to`, to be
+    /// signed on `curve`.
+    #[must_use]
+    pub fn new(from: impl Into<String>, to: impl Into<String>, amount: u128, curve: Curve) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+            amount,
+            curve,
+            nonce: None,
+            fee: None,
+            signature: None,
+        }
+    }
+
+    /// Sender address.
+    #[must_use]
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    /// Recipient address.
+    #[must_use]
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+
+    /// Amount to transfer, in the chain's smallest unit.
+    #[must_use]
+    pub fn amount(&self) -> u128 {
+        self.amount
+    }
+
+    /// Nonce filled in by a [`crate::middleware::NonceManager`], if any.
+    #[must_use]
+    pub fn nonce(&self) -> Option<u64> {
+        self.nonce
+    }
+
+    /// Fee/gas price filled in by a [`crate::middleware::GasOracle`], if any.
+    #[must_use]
+    pub fn fee(&self) -> Option<u128> {
+        self.fee
+    }
+
+    /// Assign the resolved nonce.
+    pub fn set_nonce(&mut self, nonce: u64) {
+        self.nonce = Some(nonce);
+    }
+
+    /// Assign the resolved fee/gas price.
+    pub fn set_fee(&mut self, fee: u128) {
+        self.fee = Some(fee);
+    }
+
+    /// The signature produced by [`Self::sign`], if any.
+    #[must_use]
+    pub fn signature(&self) -> Option<&[u8]> {
+        self.signature.as_deref()
+    }
+
+    /// The bytes a [`crate::Signer`] signs over: every field but the
+    /// signature itself.
+    fn signing_payload(&self) -> Vec<u8> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(self.from.as_bytes());
+        payload.extend_from_slice(self.to.as_bytes());
+        payload.extend_from_slice(&self.amount.to_be_bytes());
+        payload.extend_from_slice(&self.nonce.unwrap_or_default().to_be_bytes());
+        payload.extend_from_slice(&self.fee.unwrap_or_default().to_be_bytes());
+        payload
+    }
+
+    /// Sign this transaction with `signer`, filling in its signature.
+    /// Requires [`Self::set_nonce`] and [`Self::set_fee`] to have already
+    /// run (the middleware stack's `fill_transaction` step).
+    pub fn sign(mut self, signer: &Signer) -> anyhow::Result<Self> {
+        let payload = self.signing_payload();
+        self.signature = Some(signer.sign(self.curve, &payload)?);
+        Ok(self)
+    }
+}
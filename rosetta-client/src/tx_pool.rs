@@ -0,0 +1,430 @@
+//! Client-side pending-transaction pool used by [`crate::Wallet`] to let
+//! callers submit many transactions and have them released in nonce order.
+//!
+//! Modeled on OpenEthereum's transaction queue: every transaction is keyed by
+//! `(sender, nonce)` and partitioned into "ready" (the next expected nonce for
+//! its sender, contiguous with whatever is already ready) and "future" (a
+//! nonce gap exists before it). Promotions from future to ready happen as
+//! soon as the missing nonces are submitted.
+use std::collections::{BTreeMap, HashMap};
+
+/// A pooled transaction, identified by sender and nonce and ordered within a
+/// sender by a caller-supplied score (typically fee/gas-price).
+#[derive(Clone, Debug)]
+pub struct PooledTransaction<T> {
+    sender: String,
+    nonce: u64,
+    score: u64,
+    inner: T,
+}
+
+impl<T> PooledTransaction<T> {
+    /// Wrap `inner` for pooling under `sender`/`nonce`, ranked by `score`.
+    pub fn new(sender: String, nonce: u64, score: u64, inner: T) -> Self {
+        Self {
+            sender,
+            nonce,
+            score,
+            inner,
+        }
+    }
+
+    /// The wrapped transaction.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// The nonce this transaction is pooled under.
+    #[must_use]
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Consume the wrapper, returning the wrapped transaction.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+/// Configuration limits for a [`TxPool`].
+#[derive(Clone, Copy, Debug)]
+pub struct TxPoolConfig {
+    /// Maximum number of pending transactions a single sender may occupy.
+    pub per_sender_cap: usize,
+    /// Maximum number of pending transactions across all senders.
+    pub nonce_cap: usize,
+    /// Minimum score increase required to replace a transaction already
+    /// occupying a given `(sender, nonce)` slot.
+    pub min_score_bump: u64,
+}
+
+impl Default for TxPoolConfig {
+    fn default() -> Self {
+        Self {
+            per_sender_cap: 64,
+            nonce_cap: 4096,
+            min_score_bump: 1,
+        }
+    }
+}
+
+/// Why a transaction was dropped from the pool.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DropReason {
+    /// The same `(sender, nonce)` slot is held by a transaction this one does
+    /// not outscore by at least `min_score_bump`, or this one was replaced by
+    /// a transaction that did.
+    Underpriced,
+    /// The sender already has `per_sender_cap` transactions pending.
+    SenderCapExceeded,
+    /// The pool is full and this transaction ranked lowest (penalized
+    /// senders first, then by score).
+    Evicted,
+}
+
+/// Callback invoked when a transaction's state changes.
+pub trait PoolListener<T>: Send + Sync {
+    /// Called when a transaction becomes its sender's next expected nonce:
+    /// when that nonce is submitted, or when [`TxPool::cull`] advances the
+    /// expected nonce onto an already-pooled transaction. Transactions queued
+    /// contiguously behind it are not reported, and [`TxPool::next_ready`]
+    /// does not fire this.
+    fn on_ready(&self, _tx: &PooledTransaction<T>) {}
+    /// Called when a transaction is dropped instead of accepted.
+    fn on_dropped(&self, _tx: &PooledTransaction<T>, _reason: DropReason) {}
+}
+
+struct SenderQueue<T> {
+    /// Transactions for this sender, keyed by nonce.
+    by_nonce: BTreeMap<u64, PooledTransaction<T>>,
+    /// Next nonce this sender is expected to use (i.e. the last confirmed
+    /// nonce, plus one).
+    expected_nonce: u64,
+    /// Penalized senders' transactions are evicted before any non-penalized
+    /// sender's when the pool must make room.
+    penalized: bool,
+}
+
+/// A pending-transaction pool that releases transactions to callers in
+/// nonce order, per sender.
+pub struct TxPool<T> {
+    config: TxPoolConfig,
+    senders: HashMap<String, SenderQueue<T>>,
+    listeners: Vec<Box<dyn PoolListener<T>>>,
+    len: usize,
+}
+
+impl<T> TxPool<T> {
+    /// Create an empty pool with `config`.
+    pub fn new(config: TxPoolConfig) -> Self {
+        Self {
+            config,
+            senders: HashMap::new(),
+            listeners: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Register a listener notified of ready/dropped transactions.
+    pub fn add_listener(&mut self, listener: Box<dyn PoolListener<T>>) {
+        self.listeners.push(listener);
+    }
+
+    /// Number of transactions currently pooled, ready or future.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the pool holds no transactions.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Mark a sender as misbehaving, so its transactions are evicted before
+    /// any non-penalized sender's when the pool is full.
+    pub fn penalize(&mut self, sender: &str) {
+        if let Some(queue) = self.senders.get_mut(sender) {
+            queue.penalized = true;
+        }
+    }
+
+    /// Submit `tx` to the pool. Returns `false` if it was rejected (and a
+    /// listener is notified with the reason).
+    pub fn submit(&mut self, tx: PooledTransaction<T>) -> bool {
+        let queue = self.senders.entry(tx.sender.clone()).or_insert_with(|| SenderQueue {
+            by_nonce: BTreeMap::new(),
+            // A sender with no tracked nonce yet hasn't confirmed anything,
+            // so it's expected to start at 0, not whatever nonce happens to
+            // arrive first (which would make an arbitrary first submission
+            // "ready" regardless of its actual value).
+            expected_nonce: 0,
+            penalized: false,
+        });
+
+        let replaces_own_slot = queue.by_nonce.contains_key(&tx.nonce);
+        if let Some(existing) = queue.by_nonce.get(&tx.nonce) {
+            if tx.score < existing.score.saturating_add(self.config.min_score_bump) {
+                self.notify_dropped(&tx, DropReason::Underpriced);
+                return false;
+            }
+        } else if queue.by_nonce.len() >= self.config.per_sender_cap {
+            self.notify_dropped(&tx, DropReason::SenderCapExceeded);
+            return false;
+        }
+
+        // A fee bump replacing its own (sender, nonce) slot doesn't
+        // net-increase `len`, so it must not trigger eviction of an
+        // unrelated, still-valid transaction.
+        if !replaces_own_slot && self.len >= self.config.nonce_cap && !self.evict_lowest_scored(&tx) {
+            self.notify_dropped(&tx, DropReason::Evicted);
+            return false;
+        }
+
+        let queue = self.senders.get_mut(&tx.sender).unwrap();
+        let sender = tx.sender.clone();
+        let nonce = tx.nonce;
+        let becomes_ready = nonce == queue.expected_nonce;
+        match queue.by_nonce.insert(nonce, tx) {
+            Some(replaced) => self.notify_dropped(&replaced, DropReason::Underpriced),
+            None => self.len += 1,
+        }
+        // Only fire when this submission is the one that lands in the
+        // ready slot: an unrelated later submit for the same sender must
+        // not re-fire `on_ready` for a transaction that was already ready.
+        if becomes_ready {
+            self.notify_ready_from(&sender);
+        }
+        true
+    }
+
+    /// Evict the lowest-ranked transaction (any penalized sender's before
+    /// any other's, then lowest score first) to make room for `incoming`,
+    /// unless that transaction's score is at least `incoming`'s. Returns
+    /// whether room was made.
+    fn evict_lowest_scored(&mut self, incoming: &PooledTransaction<T>) -> bool {
+        let worst = self
+            .senders
+            .iter()
+            .flat_map(|(sender, queue)| {
+                queue
+                    .by_nonce
+                    .values()
+                    .map(move |tx| (sender.clone(), tx.nonce, tx.score, queue.penalized))
+            })
+            .max_by_key(|(_, _, score, penalized)| (*penalized, u64::MAX - score));
+
+        let Some((sender, nonce, score, _)) = worst else {
+            return false;
+        };
+        if score >= incoming.score {
+            return false;
+        }
+        if let Some(queue) = self.senders.get_mut(&sender) {
+            if let Some(evicted) = queue.by_nonce.remove(&nonce) {
+                self.len -= 1;
+                self.notify_dropped(&evicted, DropReason::Evicted);
+            }
+        }
+        true
+    }
+
+    /// Pop the next ready transaction for `sender`, i.e. the one whose nonce
+    /// equals `sender`'s expected next nonce, if any.
+    pub fn next_ready(&mut self, sender: &str) -> Option<PooledTransaction<T>> {
+        let queue = self.senders.get_mut(sender)?;
+        let tx = queue.by_nonce.remove(&queue.expected_nonce)?;
+        queue.expected_nonce += 1;
+        self.len -= 1;
+        Some(tx)
+    }
+
+    /// Drop every transaction for `sender` up to and including
+    /// `confirmed_nonce`, advancing the expected nonce past it.
+    pub fn cull(&mut self, sender: &str, confirmed_nonce: u64) {
+        let Some(queue) = self.senders.get_mut(sender) else {
+            return;
+        };
+        let stale: Vec<u64> = queue
+            .by_nonce
+            .range(..=confirmed_nonce)
+            .map(|(nonce, _)| *nonce)
+            .collect();
+        for nonce in stale {
+            queue.by_nonce.remove(&nonce);
+            self.len -= 1;
+        }
+        queue.expected_nonce = queue.expected_nonce.max(confirmed_nonce + 1);
+        // A confirmation may close the gap in front of an already-pooled
+        // future transaction; let listeners know it's ready now.
+        self.notify_ready_from(sender);
+    }
+
+    fn notify_ready_from(&self, sender: &str) {
+        let Some(queue) = self.senders.get(sender) else {
+            return;
+        };
+        if let Some(tx) = queue.by_nonce.get(&queue.expected_nonce) {
+            for listener in &self.listeners {
+                listener.on_ready(tx);
+            }
+        }
+    }
+
+    fn notify_dropped(&self, tx: &PooledTransaction<T>, reason: DropReason) {
+        for listener in &self.listeners {
+            listener.on_dropped(tx, reason);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct RecordingListener {
+        ready: Mutex<Vec<(String, u64)>>,
+        dropped: Mutex<Vec<(String, u64, DropReason)>>,
+    }
+
+    impl PoolListener<&'static str> for RecordingListener {
+        fn on_ready(&self, tx: &PooledTransaction<&'static str>) {
+            self.ready.lock().unwrap().push((tx.sender.clone(), tx.nonce));
+        }
+
+        fn on_dropped(&self, tx: &PooledTransaction<&'static str>, reason: DropReason) {
+            self.dropped
+                .lock()
+                .unwrap()
+                .push((tx.sender.clone(), tx.nonce, reason));
+        }
+    }
+
+    impl PoolListener<&'static str> for Arc<RecordingListener> {
+        fn on_ready(&self, tx: &PooledTransaction<&'static str>) {
+            (**self).on_ready(tx);
+        }
+
+        fn on_dropped(&self, tx: &PooledTransaction<&'static str>, reason: DropReason) {
+            (**self).on_dropped(tx, reason);
+        }
+    }
+
+    fn tx(sender: &str, nonce: u64, score: u64) -> PooledTransaction<&'static str> {
+        PooledTransaction::new(sender.to_string(), nonce, score, "tx")
+    }
+
+    #[test]
+    fn promotes_future_tx_to_ready_as_gap_fills() {
+        let mut pool = TxPool::new(TxPoolConfig::default());
+        let listener = Arc::new(RecordingListener::default());
+        pool.add_listener(Box::new(listener.clone()));
+
+        // Nonce 1 arrives first: it's "future" (expected nonce is 0), so it
+        // must not fire `on_ready`.
+        assert!(pool.submit(tx("alice", 1, 10)));
+        assert!(listener.ready.lock().unwrap().is_empty());
+
+        // Submitting nonce 0 closes the gap and is itself ready immediately.
+        assert!(pool.submit(tx("alice", 0, 10)));
+        assert_eq!(*listener.ready.lock().unwrap(), vec![("alice".to_string(), 0)]);
+
+        // Nonce 1 is now contiguous behind it, so both release in order.
+        assert_eq!(pool.next_ready("alice").unwrap().nonce, 0);
+        assert_eq!(pool.next_ready("alice").unwrap().nonce, 1);
+    }
+
+    #[test]
+    fn submit_only_fires_on_ready_once_edge_triggered() {
+        let mut pool = TxPool::new(TxPoolConfig::default());
+        let listener = Arc::new(RecordingListener::default());
+        pool.add_listener(Box::new(listener.clone()));
+
+        assert!(pool.submit(tx("alice", 0, 10)));
+        // A later, unrelated submission for the same sender must not
+        // re-fire `on_ready` for the already-ready nonce 0 transaction.
+        assert!(pool.submit(tx("alice", 2, 10)));
+        assert_eq!(*listener.ready.lock().unwrap(), vec![("alice".to_string(), 0)]);
+    }
+
+    #[test]
+    fn cull_promotes_the_next_tx_once_gaps_fill() {
+        let mut pool = TxPool::new(TxPoolConfig::default());
+        let listener = Arc::new(RecordingListener::default());
+        pool.add_listener(Box::new(listener.clone()));
+
+        assert!(pool.submit(tx("alice", 0, 10)));
+        assert!(pool.submit(tx("alice", 1, 10)));
+        listener.ready.lock().unwrap().clear();
+
+        // Confirming nonce 0 makes nonce 1 the new expected nonce, which is
+        // already pooled: cull must promote it to ready.
+        pool.cull("alice", 0);
+        assert_eq!(*listener.ready.lock().unwrap(), vec![("alice".to_string(), 1)]);
+    }
+
+    #[test]
+    fn fee_bump_replaces_slot_and_drops_the_old_tx() {
+        let mut pool = TxPool::new(TxPoolConfig::default());
+        let listener = Arc::new(RecordingListener::default());
+        pool.add_listener(Box::new(listener.clone()));
+
+        assert!(pool.submit(tx("alice", 0, 10)));
+        assert!(pool.submit(tx("alice", 0, 20)));
+        assert_eq!(pool.len(), 1);
+        assert_eq!(
+            *listener.dropped.lock().unwrap(),
+            vec![("alice".to_string(), 0, DropReason::Underpriced)]
+        );
+    }
+
+    #[test]
+    fn underpriced_replacement_is_rejected() {
+        let mut pool = TxPool::new(TxPoolConfig::default());
+        assert!(pool.submit(tx("alice", 0, 10)));
+        assert!(!pool.submit(tx("alice", 0, 10)));
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn evicts_globally_lowest_scored_when_full() {
+        let mut pool = TxPool::new(TxPoolConfig {
+            nonce_cap: 2,
+            ..TxPoolConfig::default()
+        });
+        assert!(pool.submit(tx("alice", 0, 5)));
+        assert!(pool.submit(tx("bob", 0, 1)));
+        // Pool is full; a higher-scored tx evicts bob's lower-scored one.
+        assert!(pool.submit(tx("carol", 0, 10)));
+        assert_eq!(pool.len(), 2);
+        assert!(pool.next_ready("bob").is_none());
+        assert!(pool.next_ready("carol").is_some());
+    }
+
+    #[test]
+    fn fee_bump_does_not_evict_an_unrelated_tx() {
+        let mut pool = TxPool::new(TxPoolConfig {
+            nonce_cap: 2,
+            ..TxPoolConfig::default()
+        });
+        assert!(pool.submit(tx("alice", 0, 10)));
+        assert!(pool.submit(tx("bob", 0, 5)));
+        // A fee bump of alice's own slot doesn't net-increase `len`, so it
+        // must not evict bob even though the pool is at capacity.
+        assert!(pool.submit(tx("alice", 0, 100)));
+        assert_eq!(pool.len(), 2);
+        assert!(pool.next_ready("bob").is_some());
+    }
+
+    #[test]
+    fn next_ready_releases_in_nonce_order() {
+        let mut pool = TxPool::new(TxPoolConfig::default());
+        assert!(pool.submit(tx("alice", 0, 10)));
+        assert!(pool.submit(tx("alice", 1, 10)));
+        assert_eq!(pool.next_ready("alice").unwrap().nonce, 0);
+        assert_eq!(pool.next_ready("alice").unwrap().nonce, 1);
+        assert!(pool.next_ready("alice").is_none());
+    }
+}
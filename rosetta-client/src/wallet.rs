@@ -0,0 +1,99 @@
+//! The Rosetta-client wallet: signs and submits transactions through a
+//! [`crate::middleware`] stack (nonce assignment, fee estimation, signing)
+//! built once around a connector, queuing filled-in transactions through a
+//! [`TxPool`] so sends for the same sender always broadcast in nonce order.
+//!
+//! This nonce-centric design covers [`crate::Blockchain::Ethereum`] and
+//! [`crate::Blockchain::Polkadot`]/`Astar`. Bitcoin has no nonce, so
+//! [`crate::Blockchain::Bitcoin`] is driven by [`crate::BitcoinWallet`]
+//! instead of a `Wallet<C>` - see its module docs for why that's a sibling
+//! type rather than another `Middleware` stack plugged in here.
+use crate::middleware::{GasOracle, Middleware, NonceManager, SignerMiddleware};
+use crate::tx_builder::UnsignedTransaction;
+use crate::tx_pool::{PooledTransaction, TxPool, TxPoolConfig};
+use crate::Signer;
+use anyhow::Result;
+use rosetta_core::types::TransactionIdentifier;
+
+/// A wallet: a connector driven through the standard nonce/fee/signer
+/// middleware stack, queuing filled-in transactions through a [`TxPool`] so
+/// callers never have to fill those fields, sign, or order concurrent sends
+/// themselves.
+pub struct Wallet<C: Middleware>
+where
+    C::Error: From<anyhow::Error>,
+{
+    stack: NonceManager<GasOracle<SignerMiddleware<C>>>,
+    pool: tokio::sync::Mutex<TxPool<UnsignedTransaction>>,
+}
+
+impl<C: Middleware> Wallet<C>
+where
+    C::Error: From<anyhow::Error>,
+{
+    /// Build a wallet driving `client` through the standard
+    /// `NonceManager(GasOracle(SignerMiddleware(client)))` stack, signing
+    /// with `signer`. The wallet keeps its own copy of `signer`.
+    pub fn new(client: C, signer: &Signer) -> Result<Self> {
+        Ok(Self {
+            stack: NonceManager::new(GasOracle::new(SignerMiddleware::new(client, signer.clone()))),
+            pool: tokio::sync::Mutex::new(TxPool::new(TxPoolConfig::default())),
+        })
+    }
+
+    /// Build, fill in, and queue a transfer of `amount` from `from` to `to`
+    /// through this wallet's [`TxPool`], then broadcast it (and any other
+    /// transaction for `from` the queue just released) in nonce order.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the pool rejects the transaction (e.g. `from` already has
+    /// `TxPoolConfig::per_sender_cap` pending), or if `from` has an earlier
+    /// nonce still pending elsewhere, so this transaction is queued rather
+    /// than broadcast yet. In the latter case the transaction isn't lost: it
+    /// broadcasts once the gap closes, from whichever `send` call closes it.
+    /// That call returns only its own transaction's identifier; the queued
+    /// transaction's identifier is not returned to any caller.
+    pub async fn send(
+        &self,
+        from: &str,
+        to: &str,
+        amount: u128,
+    ) -> Result<TransactionIdentifier, C::Error> {
+        let mut tx = UnsignedTransaction::new(from, to, amount, self.stack.curve());
+        self.stack.fill_transaction(&mut tx).await?;
+        let nonce = tx.nonce().unwrap_or_default();
+        let score = u64::try_from(tx.fee().unwrap_or_default()).unwrap_or(u64::MAX);
+
+        let ready = {
+            let mut pool = self.pool.lock().await;
+            if !pool.submit(PooledTransaction::new(from.to_string(), nonce, score, tx)) {
+                return Err(anyhow::anyhow!(
+                    "transaction pool rejected this send for {from}"
+                )
+                .into());
+            }
+            let mut ready = Vec::new();
+            while let Some(tx) = pool.next_ready(from) {
+                ready.push(tx);
+            }
+            ready
+        };
+
+        let mut own_identifier = None;
+        for tx in ready {
+            let is_ours = tx.nonce() == nonce;
+            let identifier = self.stack.send_transaction(tx.into_inner()).await?;
+            if is_ours {
+                own_identifier = Some(identifier);
+            }
+        }
+
+        own_identifier.ok_or_else(|| {
+            anyhow::anyhow!(
+                "transaction for {from} queued at nonce {nonce}, waiting on an earlier pending nonce"
+            )
+            .into()
+        })
+    }
+}
@@ -0,0 +1,52 @@
+//! The trait every chain connector implements.
+use crate::config::BlockchainConfig;
+use crate::types::TransactionIdentifier;
+use anyhow::Result;
+use async_trait::async_trait;
+use rosetta_types::CallRequest;
+use serde_json::Value;
+
+/// On-chain account state [`BlockchainClient::account`] looks up, so callers
+/// building a transaction (e.g. a nonce manager) don't have to know how each
+/// chain tracks it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Account {
+    /// The account's next usable nonce.
+    pub nonce: u64,
+}
+
+/// What every chain connector exposes to `rosetta-client`'s middleware stack
+/// and `rosetta-docker`'s test harness.
+#[async_trait]
+pub trait BlockchainClient: Send + Sync {
+    /// This connector's config, as given to it at startup.
+    fn config(&self) -> &BlockchainConfig;
+
+    /// Look up `address`'s on-chain account state.
+    async fn account(&self, address: &str) -> Result<Account>;
+
+    /// Estimate a fee/gas price for moving `amount` from `from` to `to`.
+    async fn fee_estimate(&self, from: &str, to: &str, amount: u128) -> Result<u128>;
+
+    /// Submit a signed transfer, returning its identifier.
+    async fn submit(
+        &self,
+        from: &str,
+        to: &str,
+        amount: u128,
+        signature: &[u8],
+    ) -> Result<TransactionIdentifier>;
+
+    /// Handle a `/call` request for a network-specific method this connector
+    /// defines (e.g. Ethereum's `deployer.*`/`router.*` methods), beyond the
+    /// chain-agnostic surface above. Connectors with no such methods can
+    /// leave this as is; the error names the method so the caller can tell
+    /// "unsupported" from an actual failure of a real method.
+    async fn call(&self, request: &CallRequest) -> Result<Value> {
+        anyhow::bail!(
+            "{} does not support /call method {}",
+            self.config().blockchain,
+            request.method
+        )
+    }
+}
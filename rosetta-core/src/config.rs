@@ -0,0 +1,85 @@
+//! Chain-agnostic description of how to run a chain's node and reach it,
+//! built by the `rosetta-config-*` crates and consumed by
+//! [`crate::BlockchainClient`] implementations and `rosetta-docker`'s test
+//! harness.
+
+/// Where a connector reaches its node once it's started.
+#[derive(Clone, Copy, Debug)]
+pub struct NodeUri {
+    /// URI scheme, e.g. `"http"` or `"ws"`.
+    pub scheme: &'static str,
+    /// Host the node listens on.
+    pub host: &'static str,
+    /// Port the node listens on.
+    pub port: u16,
+}
+
+impl NodeUri {
+    /// Same endpoint, reached over `scheme` instead.
+    #[must_use]
+    pub fn with_scheme(mut self, scheme: &'static str) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Same endpoint, reached at `host` instead.
+    #[must_use]
+    pub fn with_host(mut self, host: &'static str) -> Self {
+        self.host = host;
+        self
+    }
+}
+
+impl std::fmt::Display for NodeUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}://{}:{}", self.scheme, self.host, self.port)
+    }
+}
+
+/// Description of an auxiliary container a `rosetta_docker::Env` should start
+/// alongside the chain node, e.g. an indexer that connects to it.
+#[derive(Clone, Copy, Debug)]
+pub struct AuxServiceConfig {
+    /// Name of the service, used for container naming and `depends_on`.
+    pub name: &'static str,
+    /// Docker image to run.
+    pub image: &'static str,
+    /// Container command, given the network name and this service's primary port.
+    pub command: fn(network: &str, port: u16) -> Vec<String>,
+    /// Primary port this service listens on and exposes to the host.
+    pub port: u16,
+    /// Names of other aux services that must be healthy before this one starts.
+    pub depends_on: &'static [&'static str],
+}
+
+/// Everything a connector needs to start its node and talk to it: which
+/// chain/network it's for, how to run the node container, and how to reach
+/// it once it's up.
+#[derive(Clone, Debug)]
+pub struct BlockchainConfig {
+    /// Canonical chain name, e.g. `"bitcoin"`.
+    pub blockchain: &'static str,
+    /// Network within the chain, e.g. `"mainnet"`, `"testnet"`.
+    pub network: &'static str,
+    /// Docker image the node runs in.
+    pub node_image: &'static str,
+    /// Builds the node container's command from its network and assigned port.
+    pub node_command: fn(network: &str, port: u16) -> Vec<String>,
+    /// Where the connector reaches the node.
+    pub node_uri: NodeUri,
+    /// Additional ports the node container exposes besides `node_uri.port`.
+    pub node_additional_ports: &'static [u16],
+    /// Auxiliary containers (e.g. an indexer) a `rosetta_docker::Env` should
+    /// start alongside the node, on a shared Docker network, in the order
+    /// their `depends_on` allows.
+    pub aux_services: Vec<AuxServiceConfig>,
+}
+
+impl BlockchainConfig {
+    /// Attach auxiliary services to start alongside the node.
+    #[must_use]
+    pub fn with_aux_services(mut self, aux_services: Vec<AuxServiceConfig>) -> Self {
+        self.aux_services = aux_services;
+        self
+    }
+}
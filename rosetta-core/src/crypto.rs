@@ -0,0 +1,3 @@
+//! Cryptographic helpers shared across connectors (address and key encoding
+//! used by more than one chain). Re-exported by `rosetta-client` for
+//! convenience.
@@ -0,0 +1,12 @@
+//! Core abstractions shared by every chain connector: a chain-agnostic
+//! [`BlockchainConfig`], the [`BlockchainClient`] trait connectors implement,
+//! and the wire types both sides agree on.
+
+mod client;
+mod config;
+
+pub mod crypto;
+pub mod types;
+
+pub use client::{Account, BlockchainClient};
+pub use config::{AuxServiceConfig, BlockchainConfig, NodeUri};
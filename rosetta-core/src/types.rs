@@ -0,0 +1,8 @@
+//! Wire types shared between connectors and `rosetta-client`.
+
+/// Identifies a submitted transaction.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionIdentifier {
+    /// Chain-specific transaction hash.
+    pub hash: String,
+}
@@ -3,12 +3,13 @@ mod config;
 use anyhow::Result;
 use docker_api::conn::TtyChunk;
 use docker_api::opts::{
-    ContainerCreateOpts, ContainerListOpts, ContainerStopOpts, HostPort, LogsOpts, PublishPort,
+    ContainerCreateOpts, ContainerListOpts, ContainerStopOpts, HostPort, LogsOpts, NetworkCreateOpts,
+    PublishPort,
 };
-use docker_api::{ApiVersion, Container, Docker};
+use docker_api::{ApiVersion, Container, Docker, Network};
 use futures::stream::StreamExt;
 use rosetta_client::{Signer, Wallet};
-use rosetta_core::{BlockchainClient, BlockchainConfig};
+use rosetta_core::{AuxServiceConfig, BlockchainClient, BlockchainConfig};
 use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
@@ -17,6 +18,8 @@ use tokio_retry::{strategy::ExponentialBackoff, RetryIf};
 pub struct Env<T> {
     client: Arc<T>,
     node: Container,
+    aux: Vec<Container>,
+    network: Option<Network>,
 }
 
 impl<T: BlockchainClient> Env<T> {
@@ -35,7 +38,25 @@ impl<T: BlockchainClient> Env<T> {
         config.node_uri.port = node_port;
         log::info!("node: {}", node_port);
         builder.stop_container(&builder.node_name(&config)).await?;
-        let node = builder.run_node(&config).await?;
+
+        // Requires `BlockchainConfig::aux_services` (added in rosetta-core
+        // alongside `with_aux_services`); any future field this env setup
+        // depends on should land in the same commit as the code using it, not
+        // be patched in afterward once the gap is noticed.
+        let network = if config.aux_services.is_empty() {
+            None
+        } else {
+            Some(builder.create_network(&config).await?)
+        };
+        let node = builder.run_node(&config, network.as_ref()).await?;
+        let aux = match builder.run_aux_services(&config, network.as_ref()).await {
+            Ok(aux) => aux,
+            Err(e) => {
+                let opts = ContainerStopOpts::builder().build();
+                let _ = node.stop(&opts).await;
+                return Err(e);
+            }
+        };
 
         let client = match builder
             .run_connector::<T, Fut, F>(start_connector, config)
@@ -44,7 +65,9 @@ impl<T: BlockchainClient> Env<T> {
             Ok(connector) => connector,
             Err(e) => {
                 let opts = ContainerStopOpts::builder().build();
-                let _ = node.stop(&opts).await;
+                for container in aux.iter().chain(std::iter::once(&node)) {
+                    let _ = container.stop(&opts).await;
+                }
                 return Err(e);
             }
         };
@@ -52,6 +75,8 @@ impl<T: BlockchainClient> Env<T> {
         Ok(Self {
             client: Arc::new(client),
             node,
+            aux,
+            network,
         })
     }
 
@@ -66,7 +91,14 @@ impl<T: BlockchainClient> Env<T> {
 
     pub async fn shutdown(self) -> Result<()> {
         let opts = ContainerStopOpts::builder().build();
+        // Tear down dependants before the node they connect to.
+        for container in self.aux {
+            container.stop(&opts).await?;
+        }
         self.node.stop(&opts).await?;
+        if let Some(network) = self.network {
+            network.delete().await?;
+        }
         Ok(())
     }
 }
@@ -119,6 +151,20 @@ impl<'a> EnvBuilder<'a> {
         Ok(())
     }
 
+    fn network_name(&self, config: &BlockchainConfig) -> String {
+        format!("{}-net-{}-{}", self.prefix, config.blockchain, config.network)
+    }
+
+    /// Create a Docker network so the node and its auxiliary services can
+    /// reach each other by container name.
+    async fn create_network(&self, config: &BlockchainConfig) -> Result<Network> {
+        let name = self.network_name(config);
+        log::info!("creating network {}", name);
+        let opts = NetworkCreateOpts::builder(&name).build();
+        let id = self.docker.networks().create(&opts).await?.id().clone();
+        Ok(Network::new(self.docker.clone(), id))
+    }
+
     async fn run_container(&self, name: String, opts: &ContainerCreateOpts) -> Result<Container> {
         log::info!("creating {}", name);
         let id = self.docker.containers().create(opts).await?.id().clone();
@@ -168,7 +214,7 @@ impl<'a> EnvBuilder<'a> {
         Ok(container)
     }
 
-    async fn run_node(&self, config: &BlockchainConfig) -> Result<Container> {
+    async fn run_node(&self, config: &BlockchainConfig, network: Option<&Network>) -> Result<Container> {
         let name = self.node_name(config);
         let mut opts = ContainerCreateOpts::builder()
             .name(&name)
@@ -186,7 +232,10 @@ impl<'a> EnvBuilder<'a> {
             let port = *port as u32;
             opts = opts.expose(PublishPort::tcp(port), port);
         }
-        let container = self.run_container(name, &opts.build()).await?;
+        let container = self.run_container(name.clone(), &opts.build()).await?;
+        if let Some(network) = network {
+            network.connect(&docker_api::opts::ContainerConnectionOpts::builder(&container.id()).build()).await?;
+        }
 
         // TODO: replace this by a proper healthcheck
         let maybe_error = if matches!(config.node_uri.scheme, "http" | "https" | "ws" | "wss") {
@@ -214,6 +263,51 @@ impl<'a> EnvBuilder<'a> {
         Ok(container)
     }
 
+    /// Start every auxiliary service declared on `config`, in dependency
+    /// order, attaching each to `network` so it can reach the node (and any
+    /// earlier aux services) by container name.
+    async fn run_aux_services(
+        &self,
+        config: &BlockchainConfig,
+        network: Option<&Network>,
+    ) -> Result<Vec<Container>> {
+        let mut started = Vec::new();
+        let mut remaining: Vec<&AuxServiceConfig> = config.aux_services.iter().collect();
+        while !remaining.is_empty() {
+            let ready_index = remaining.iter().position(|service| {
+                service
+                    .depends_on
+                    .iter()
+                    .all(|dep| started.iter().any(|(name, _)| name == dep))
+            });
+            let Some(index) = ready_index else {
+                anyhow::bail!("aux service dependency cycle or unknown dependency");
+            };
+            let service = remaining.remove(index);
+            let name = format!("{}-{}-{}", self.prefix, config.network, service.name);
+            let opts = ContainerCreateOpts::builder()
+                .name(&name)
+                .image(service.image)
+                .command((service.command)(config.network, service.port))
+                .auto_remove(true)
+                .attach_stdout(true)
+                .attach_stderr(true)
+                .expose(
+                    PublishPort::tcp(service.port as _),
+                    HostPort::new(service.port as u32),
+                )
+                .build();
+            let container = self.run_container(name, &opts).await?;
+            if let Some(network) = network {
+                network
+                    .connect(&docker_api::opts::ContainerConnectionOpts::builder(&container.id()).build())
+                    .await?;
+            }
+            started.push((service.name, container));
+        }
+        Ok(started.into_iter().map(|(_, container)| container).collect())
+    }
+
     async fn run_connector<T, Fut, F>(
         &self,
         mut start_connector: F,